@@ -142,6 +142,24 @@ impl OperationAttributes for AttributeHashMap {
   fn extend(&mut self, other: Self) {
     self.0.extend(other.0);
   }
+
+  fn diff(&self, other: &Self) -> Self {
+    let mut attributes = other
+      .iter()
+      .fold(AttributeHashMap::new(), |mut attributes, (k, v)| {
+        if self.get(k) != Some(v) {
+          attributes.insert(k.clone(), v.clone());
+        }
+        attributes
+      });
+
+    self.keys().for_each(|k| {
+      if !other.contains_key(k) {
+        attributes.remove_value(k);
+      }
+    });
+    attributes
+  }
 }
 
 impl OperationTransform for AttributeHashMap {
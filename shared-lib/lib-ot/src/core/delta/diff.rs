@@ -0,0 +1,204 @@
+/// A run of the text diff produced by [diff_str]. The length of each variant is measured in
+/// UTF-16 code units, so it can be fed into the [DeltaOperations] directly.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum DiffChunk {
+  Equal(usize),
+  Insert(usize),
+  Delete(usize),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Edit {
+  Equal(char),
+  Insert(char),
+  Delete(char),
+}
+
+/// Computes the shortest edit script that turns `base` into `target` using the linear space
+/// variant of Myers' algorithm. The strings are compared character by character, so a chunk
+/// never splits a surrogate pair.
+pub(crate) fn diff_str(base: &str, target: &str) -> Vec<DiffChunk> {
+  let a = base.chars().collect::<Vec<_>>();
+  let b = target.chars().collect::<Vec<_>>();
+  let mut edits = Vec::with_capacity(a.len().max(b.len()));
+  shortest_edit(&a, &b, &mut edits);
+
+  let mut chunks: Vec<DiffChunk> = vec![];
+  for edit in edits {
+    let chunk = match edit {
+      Edit::Equal(c) => DiffChunk::Equal(c.len_utf16()),
+      Edit::Insert(c) => DiffChunk::Insert(c.len_utf16()),
+      Edit::Delete(c) => DiffChunk::Delete(c.len_utf16()),
+    };
+    match (chunks.last_mut(), chunk) {
+      (Some(DiffChunk::Equal(n)), DiffChunk::Equal(len))
+      | (Some(DiffChunk::Insert(n)), DiffChunk::Insert(len))
+      | (Some(DiffChunk::Delete(n)), DiffChunk::Delete(len)) => *n += len,
+      _ => chunks.push(chunk),
+    }
+  }
+  chunks
+}
+
+fn shortest_edit(a: &[char], b: &[char], edits: &mut Vec<Edit>) {
+  let prefix = a.iter().zip(b.iter()).take_while(|(l, r)| l == r).count();
+  let suffix = a[prefix..]
+    .iter()
+    .rev()
+    .zip(b[prefix..].iter().rev())
+    .take_while(|(l, r)| l == r)
+    .count();
+
+  edits.extend(a[..prefix].iter().map(|c| Edit::Equal(*c)));
+  let (middle_a, middle_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+  if middle_a.is_empty() || middle_b.is_empty() {
+    edits.extend(middle_a.iter().map(|c| Edit::Delete(*c)));
+    edits.extend(middle_b.iter().map(|c| Edit::Insert(*c)));
+  } else {
+    match middle_snake(middle_a, middle_b) {
+      Some((x, y)) => {
+        shortest_edit(&middle_a[..x], &middle_b[..y], edits);
+        shortest_edit(&middle_a[x..], &middle_b[y..], edits);
+      },
+      None => {
+        edits.extend(middle_a.iter().map(|c| Edit::Delete(*c)));
+        edits.extend(middle_b.iter().map(|c| Edit::Insert(*c)));
+      },
+    }
+  }
+  edits.extend(a[a.len() - suffix..].iter().map(|c| Edit::Equal(*c)));
+}
+
+/// Searches the forward and the reverse paths at the same time and returns the point where they
+/// overlap. The edit script is split at that point, so only the furthest reaching x of each
+/// diagonal needs to be kept, which takes O(N + M) space.
+///
+/// Returns None if the strings have nothing in common.
+fn middle_snake(a: &[char], b: &[char]) -> Option<(usize, usize)> {
+  let n = a.len() as isize;
+  let m = b.len() as isize;
+  let max_d = (n + m + 1) / 2;
+  let offset = max_d + 1;
+  let len = (2 * offset + 1) as usize;
+
+  // forward[k] and reverse[k] store the furthest x reached on diagonal k (k = x - y). They are
+  // offset so that negative diagonals can be indexed. -1 means the diagonal isn't reached yet.
+  let mut forward = vec![-1_isize; len];
+  let mut reverse = vec![-1_isize; len];
+  forward[(offset + 1) as usize] = 0;
+  reverse[(offset + 1) as usize] = 0;
+
+  let delta = n - m;
+  // The paths overlap on the forward pass if the delta is odd, otherwise on the reverse pass.
+  let front = delta % 2 != 0;
+  let (mut k1_start, mut k1_end, mut k2_start, mut k2_end) = (0, 0, 0, 0);
+  let is_valid = |index: isize| index >= 0 && index < len as isize;
+  let split = |x: isize, y: isize| {
+    if (x == 0 && y == 0) || (x == n && y == m) {
+      None
+    } else {
+      Some((x as usize, y as usize))
+    }
+  };
+
+  for d in 0..max_d {
+    for k1 in (-d + k1_start..=d - k1_end).step_by(2) {
+      let k1_offset = offset + k1;
+      let mut x1 = if k1 == -d
+        || (k1 != d && forward[(k1_offset - 1) as usize] < forward[(k1_offset + 1) as usize])
+      {
+        forward[(k1_offset + 1) as usize]
+      } else {
+        forward[(k1_offset - 1) as usize] + 1
+      };
+      let mut y1 = x1 - k1;
+      while x1 < n && y1 < m && a[x1 as usize] == b[y1 as usize] {
+        x1 += 1;
+        y1 += 1;
+      }
+      forward[k1_offset as usize] = x1;
+      if x1 > n {
+        k1_end += 2;
+      } else if y1 > m {
+        k1_start += 2;
+      } else if front {
+        let k2_offset = offset + delta - k1;
+        if is_valid(k2_offset) && reverse[k2_offset as usize] != -1 {
+          let x2 = n - reverse[k2_offset as usize];
+          if x1 >= x2 {
+            return split(x1, y1);
+          }
+        }
+      }
+    }
+
+    for k2 in (-d + k2_start..=d - k2_end).step_by(2) {
+      let k2_offset = offset + k2;
+      let mut x2 = if k2 == -d
+        || (k2 != d && reverse[(k2_offset - 1) as usize] < reverse[(k2_offset + 1) as usize])
+      {
+        reverse[(k2_offset + 1) as usize]
+      } else {
+        reverse[(k2_offset - 1) as usize] + 1
+      };
+      let mut y2 = x2 - k2;
+      while x2 < n && y2 < m && a[(n - x2 - 1) as usize] == b[(m - y2 - 1) as usize] {
+        x2 += 1;
+        y2 += 1;
+      }
+      reverse[k2_offset as usize] = x2;
+      if x2 > n {
+        k2_end += 2;
+      } else if y2 > m {
+        k2_start += 2;
+      } else if !front {
+        let k1_offset = offset + delta - k2;
+        if is_valid(k1_offset) && forward[k1_offset as usize] != -1 {
+          let x1 = forward[k1_offset as usize];
+          let y1 = x1 - (k1_offset - offset);
+          if x1 >= n - x2 {
+            return split(x1, y1);
+          }
+        }
+      }
+    }
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{diff_str, DiffChunk};
+
+  #[test]
+  fn diff_str_test() {
+    assert_eq!(diff_str("", ""), vec![]);
+    assert_eq!(diff_str("abc", "abc"), vec![DiffChunk::Equal(3)]);
+    assert_eq!(
+      diff_str("abc", "axc"),
+      vec![
+        DiffChunk::Equal(1),
+        DiffChunk::Delete(1),
+        DiffChunk::Insert(1),
+        DiffChunk::Equal(1)
+      ]
+    );
+    assert_eq!(
+      diff_str("hello world", "hello"),
+      vec![DiffChunk::Equal(5), DiffChunk::Delete(6)]
+    );
+  }
+
+  #[test]
+  fn diff_str_utf16_len_test() {
+    assert_eq!(
+      diff_str("a😁b", "a👋b"),
+      vec![
+        DiffChunk::Equal(1),
+        DiffChunk::Delete(2),
+        DiffChunk::Insert(2),
+        DiffChunk::Equal(1)
+      ]
+    );
+  }
+}
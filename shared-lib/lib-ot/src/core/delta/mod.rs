@@ -1,6 +1,7 @@
 #![allow(clippy::module_inception)]
 mod builder;
 mod cursor;
+mod diff;
 mod iterator;
 pub mod operation;
 mod ops;
@@ -81,6 +81,9 @@ pub trait OperationAttributes:
   fn remove(&mut self) {}
 
   fn extend(&mut self, _other: Self) {}
+
+  /// Returns the attributes that turn `self` into `other` when they are composed with `self`.
+  fn diff(&self, other: &Self) -> Self;
 }
 
 /// [DeltaOperation] consists of three types.
@@ -471,7 +474,11 @@ impl fmt::Display for EmptyAttributes {
   }
 }
 
-impl OperationAttributes for EmptyAttributes {}
+impl OperationAttributes for EmptyAttributes {
+  fn diff(&self, _other: &Self) -> Self {
+    EmptyAttributes()
+  }
+}
 
 impl OperationTransform for EmptyAttributes {
   fn compose(&self, _other: &Self) -> Result<Self, OTError> {
@@ -1,3 +1,4 @@
+use crate::core::delta::diff::{diff_str, DiffChunk};
 use crate::core::delta::operation::{
   DeltaOperation, EmptyAttributes, OperationAttributes, OperationTransform,
 };
@@ -302,6 +303,102 @@ where
   pub fn content(&self) -> Result<String, OTError> {
     self.apply("")
   }
+
+  /// Returns the [Delta] that transforms this document into the [other] document. Both of them
+  /// must only contain [Insert] operations. Attribute changes on the unchanged text are
+  /// represented by [Retain] operations, and the [utf16_base_len] of the returned delta is always
+  /// equal to the length of this document.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  ///  use lib_ot::core::{DeltaBuilder, OperationTransform};
+  ///  let base = DeltaBuilder::new().insert("hello world").build();
+  ///  let target = DeltaBuilder::new().insert("hello AppFlowy").build();
+  ///  let delta = base.diff(&target).unwrap();
+  ///  assert_eq!(base.compose(&delta).unwrap(), target);
+  /// ```
+  pub fn diff(&self, other: &Self) -> Result<Self, OTError> {
+    if self
+      .ops
+      .iter()
+      .chain(other.ops.iter())
+      .any(|op| !op.is_insert())
+    {
+      return Err(
+        ErrorBuilder::new(OTErrorCode::Internal)
+          .msg("diff() called with non-document")
+          .build(),
+      );
+    }
+
+    let mut delta = DeltaOperations::default();
+    let mut iter = OperationIterator::new(self);
+    let mut other_iter = OperationIterator::new(other);
+    for chunk in diff_str(&self.content()?, &other.content()?) {
+      // Each step consumes at most the rest of the current operation, because
+      // [OperationIterator::next_op_with_len] seeks over the operations when the length is longer
+      // than the next operation.
+      match chunk {
+        DiffChunk::Insert(mut len) => {
+          while len > 0 {
+            let op_len = min(len, other_iter.next_op_len().unwrap_or(0));
+            let op = other_iter
+              .next_op_with_len(op_len)
+              .ok_or_else(|| diff_out_of_operations(len))?;
+            len -= op.len();
+            delta.add(op);
+          }
+        },
+        DiffChunk::Delete(mut len) => {
+          while len > 0 {
+            let op_len = min(len, iter.next_op_len().unwrap_or(0));
+            let op = iter
+              .next_op_with_len(op_len)
+              .ok_or_else(|| diff_out_of_operations(len))?;
+            len -= op.len();
+            delta.delete(op.len());
+          }
+        },
+        DiffChunk::Equal(mut len) => {
+          while len > 0 {
+            let op_len = min(
+              len,
+              min(
+                iter.next_op_len().unwrap_or(0),
+                other_iter.next_op_len().unwrap_or(0),
+              ),
+            );
+            match (
+              iter.next_op_with_len(op_len),
+              other_iter.next_op_with_len(op_len),
+            ) {
+              (Some(op), Some(other_op)) => {
+                let attributes = op.get_attributes().diff(&other_op.get_attributes());
+                delta.retain(op_len, attributes);
+              },
+              _ => return Err(diff_out_of_operations(len)),
+            }
+            len -= op_len;
+          }
+        },
+      }
+    }
+
+    // The chunks are computed from the content of both documents, so the delta always covers
+    // the whole document unless the operation walk above is broken.
+    if delta.utf16_base_len != self.utf16_target_len {
+      return Err(
+        ErrorBuilder::new(OTErrorCode::IncompatibleLength)
+          .msg(format!(
+            "diff base length: {}, document length: {}",
+            delta.utf16_base_len, self.utf16_target_len
+          ))
+          .build(),
+      );
+    }
+    Ok(delta)
+  }
 }
 
 impl<T> OperationTransform for DeltaOperations<T>
@@ -569,6 +666,15 @@ fn invert_other<T: OperationAttributes>(
   });
 }
 
+fn diff_out_of_operations(remaining: usize) -> OTError {
+  ErrorBuilder::new(OTErrorCode::Internal)
+    .msg(format!(
+      "diff() ran out of operations with {} remaining",
+      remaining
+    ))
+    .build()
+}
+
 fn transform_op_attribute<T: OperationAttributes>(
   left: &Option<DeltaOperation<T>>,
   right: &Option<DeltaOperation<T>>,
@@ -0,0 +1,348 @@
+use lib_ot::core::{AttributeBuilder, AttributeHashMap, DeltaBuilder, OperationTransform};
+use lib_ot::text_delta::{BuildInTextAttribute, DeltaTextOperationBuilder, DeltaTextOperations};
+
+fn assert_diff(base: &DeltaTextOperations, target: &DeltaTextOperations) -> DeltaTextOperations {
+  let delta = base.diff(target).unwrap();
+  assert_eq!(&base.compose(&delta).unwrap(), target);
+  delta
+}
+
+#[test]
+fn diff_identical_documents_test() {
+  let base = DeltaTextOperationBuilder::new()
+    .insert("hello world")
+    .build();
+  let delta = assert_diff(&base, &base.clone());
+  assert!(delta.is_noop());
+  assert_eq!(delta.utf16_base_len, base.utf16_target_len);
+}
+
+#[test]
+fn diff_insert_text_test() {
+  let base = DeltaTextOperationBuilder::new()
+    .insert("hello world")
+    .build();
+  let target = DeltaTextOperationBuilder::new()
+    .insert("hello, AppFlowy world")
+    .build();
+  let delta = assert_diff(&base, &target);
+  assert_eq!(
+    delta,
+    DeltaTextOperationBuilder::new()
+      .retain(5)
+      .insert(", AppFlowy")
+      .retain(6)
+      .build()
+  );
+}
+
+#[test]
+fn diff_delete_text_test() {
+  let base = DeltaTextOperationBuilder::new()
+    .insert("hello world")
+    .build();
+  let target = DeltaTextOperationBuilder::new().insert("hello").build();
+  let delta = assert_diff(&base, &target);
+  assert_eq!(
+    delta,
+    DeltaTextOperationBuilder::new().retain(5).delete(6).build()
+  );
+}
+
+#[test]
+fn diff_delete_all_text_test() {
+  let base = DeltaTextOperationBuilder::new()
+    .insert("hello world")
+    .build();
+  let target = DeltaTextOperationBuilder::new().build();
+  let delta = assert_diff(&base, &target);
+  assert_eq!(delta, DeltaTextOperationBuilder::new().delete(11).build());
+}
+
+#[test]
+fn diff_add_format_test() {
+  let base = DeltaTextOperationBuilder::new()
+    .insert("hello world")
+    .build();
+  let bold = AttributeBuilder::new()
+    .insert_entry(BuildInTextAttribute::Bold(true))
+    .build();
+  let target = DeltaTextOperationBuilder::new()
+    .insert("hello ")
+    .insert_with_attributes("world", bold.clone())
+    .build();
+  let delta = assert_diff(&base, &target);
+  assert_eq!(
+    delta,
+    DeltaTextOperationBuilder::new()
+      .retain(6)
+      .retain_with_attributes(5, bold)
+      .build()
+  );
+}
+
+#[test]
+fn diff_remove_format_test() {
+  let bold = AttributeBuilder::new()
+    .insert_entry(BuildInTextAttribute::Bold(true))
+    .build();
+  let base = DeltaTextOperationBuilder::new()
+    .insert_with_attributes("hello", bold)
+    .insert(" world")
+    .build();
+  let target = DeltaTextOperationBuilder::new()
+    .insert("hello world")
+    .build();
+  let delta = assert_diff(&base, &target);
+  assert_eq!(
+    delta,
+    DeltaTextOperationBuilder::new()
+      .retain_with_attributes(5, AttributeBuilder::new().delete("bold").build())
+      .retain(6)
+      .build()
+  );
+}
+
+#[test]
+fn diff_replace_format_test() {
+  let base = DeltaTextOperationBuilder::new()
+    .insert_with_attributes(
+      "hello",
+      AttributeBuilder::new()
+        .insert_entry(BuildInTextAttribute::Bold(true))
+        .build(),
+    )
+    .insert(" world")
+    .build();
+  let target = DeltaTextOperationBuilder::new()
+    .insert_with_attributes(
+      "hello",
+      AttributeBuilder::new()
+        .insert_entry(BuildInTextAttribute::Italic(true))
+        .build(),
+    )
+    .insert(" world")
+    .build();
+  assert_diff(&base, &target);
+}
+
+#[test]
+fn diff_text_and_format_test() {
+  let italic = AttributeBuilder::new()
+    .insert_entry(BuildInTextAttribute::Italic(true))
+    .build();
+  let base = DeltaTextOperationBuilder::new()
+    .insert("123")
+    .insert_with_attributes("456", italic.clone())
+    .insert("789\n")
+    .build();
+  let target = DeltaTextOperationBuilder::new()
+    .insert_with_attributes("12", italic.clone())
+    .insert("3")
+    .insert_with_attributes("abc", italic)
+    .insert("789 😁\n")
+    .build();
+  assert_diff(&base, &target);
+}
+
+#[test]
+fn diff_utf16_text_test() {
+  let base = DeltaBuilder::new().insert("👋 hello").build();
+  let target = DeltaBuilder::new().insert("👋😁 hello").build();
+  let delta = base.diff(&target).unwrap();
+  assert_eq!(
+    delta,
+    DeltaBuilder::new().retain(2).insert("😁").retain(6).build()
+  );
+  assert_eq!(base.compose(&delta).unwrap(), target);
+}
+
+#[test]
+fn diff_non_document_test() {
+  let base = DeltaTextOperationBuilder::new().insert("hello").build();
+  let other = DeltaTextOperationBuilder::new()
+    .retain(5)
+    .insert("!")
+    .build();
+  assert!(base.diff(&other).is_err());
+  assert!(other.diff(&base).is_err());
+}
+
+fn bold() -> AttributeHashMap {
+  AttributeBuilder::new()
+    .insert_entry(BuildInTextAttribute::Bold(true))
+    .build()
+}
+
+fn italic() -> AttributeHashMap {
+  AttributeBuilder::new()
+    .insert_entry(BuildInTextAttribute::Italic(true))
+    .build()
+}
+
+#[test]
+fn diff_empty_base_with_multi_op_target_test() {
+  let base = DeltaTextOperationBuilder::new().build();
+  let target = DeltaTextOperationBuilder::new()
+    .insert("ab")
+    .insert_with_attributes("cd", bold())
+    .insert("ef")
+    .build();
+  let delta = assert_diff(&base, &target);
+  assert_eq!(delta, target);
+}
+
+#[test]
+fn diff_multi_op_base_with_empty_target_test() {
+  let base = DeltaTextOperationBuilder::new()
+    .insert("ab")
+    .insert_with_attributes("cd", bold())
+    .insert_with_attributes("ef", italic())
+    .build();
+  let target = DeltaTextOperationBuilder::new().build();
+  let delta = assert_diff(&base, &target);
+  assert_eq!(delta, DeltaTextOperationBuilder::new().delete(6).build());
+}
+
+#[test]
+fn diff_delete_across_formatted_ops_test() {
+  let base = DeltaTextOperationBuilder::new()
+    .insert("ab")
+    .insert_with_attributes("cd", bold())
+    .insert_with_attributes("ef", italic())
+    .build();
+  let target = DeltaTextOperationBuilder::new()
+    .insert("a")
+    .insert_with_attributes("f", italic())
+    .build();
+  let delta = assert_diff(&base, &target);
+  assert_eq!(
+    delta,
+    DeltaTextOperationBuilder::new()
+      .retain(1)
+      .delete(4)
+      .retain(1)
+      .build()
+  );
+}
+
+#[test]
+fn diff_insert_across_formatted_ops_test() {
+  let base = DeltaTextOperationBuilder::new().insert("z").build();
+  let target = DeltaTextOperationBuilder::new()
+    .insert("ab")
+    .insert_with_attributes("cd", bold())
+    .insert("z")
+    .build();
+  let delta = assert_diff(&base, &target);
+  assert_eq!(
+    delta,
+    DeltaTextOperationBuilder::new()
+      .insert("ab")
+      .insert_with_attributes("cd", bold())
+      .retain(1)
+      .build()
+  );
+}
+
+#[test]
+fn diff_replace_across_formatted_ops_test() {
+  let base = DeltaTextOperationBuilder::new()
+    .insert_with_attributes("123", bold())
+    .insert("456")
+    .insert_with_attributes("789", italic())
+    .build();
+  let target = DeltaTextOperationBuilder::new()
+    .insert_with_attributes("1", bold())
+    .insert_with_attributes("xy", italic())
+    .insert("z")
+    .insert_with_attributes("89", bold())
+    .build();
+  assert_diff(&base, &target);
+}
+
+/// A linear congruential generator, so the tests are deterministic without pulling in a random
+/// crate.
+struct PseudoRandom(u64);
+
+impl PseudoRandom {
+  fn next(&mut self, bound: usize) -> usize {
+    self.0 = self
+      .0
+      .wrapping_mul(6364136223846793005)
+      .wrapping_add(1442695040888963407);
+    (self.0 >> 33) as usize % bound
+  }
+
+  fn text(&mut self, len: usize) -> String {
+    let alphabet = "abc xyz😁\n".chars().collect::<Vec<_>>();
+    (0..len)
+      .map(|_| alphabet[self.next(alphabet.len())])
+      .collect()
+  }
+
+  fn attributes(&mut self) -> AttributeHashMap {
+    match self.next(4) {
+      0 => AttributeHashMap::default(),
+      1 => bold(),
+      2 => italic(),
+      _ => bold().compose(&italic()).unwrap(),
+    }
+  }
+
+  /// Builds a document of [len] characters that is split into runs with mixed attributes.
+  fn document(&mut self, len: usize, max_run: usize) -> DeltaTextOperations {
+    let mut builder = DeltaTextOperationBuilder::new();
+    let mut remaining = len;
+    while remaining > 0 {
+      let run = (1 + self.next(max_run)).min(remaining);
+      let text = self.text(run);
+      builder = builder.insert_with_attributes(&text, self.attributes());
+      remaining -= run;
+    }
+    builder.build()
+  }
+
+  /// Returns a copy of the [document] with some runs removed, replaced or reformatted.
+  fn edit(&mut self, document: &DeltaTextOperations, max_run: usize) -> DeltaTextOperations {
+    let mut builder = DeltaTextOperationBuilder::new();
+    for op in &document.ops {
+      match self.next(5) {
+        0 => {},
+        1 => {
+          let len = self.next(max_run);
+          builder = builder.insert_with_attributes(&self.text(len), self.attributes());
+        },
+        2 => builder = builder.insert_with_attributes(op.get_data(), self.attributes()),
+        _ => builder = builder.insert_with_attributes(op.get_data(), op.get_attributes()),
+      }
+    }
+    builder.build()
+  }
+}
+
+#[test]
+fn diff_random_multi_op_documents_test() {
+  let mut random = PseudoRandom(1);
+  for _ in 0..2_000 {
+    let len = random.next(40);
+    let base = random.document(len, 6);
+    let target = if random.next(4) == 0 {
+      let len = random.next(40);
+      random.document(len, 6)
+    } else {
+      random.edit(&base, 6)
+    };
+    assert_diff(&base, &target);
+  }
+}
+
+#[test]
+fn diff_large_divergent_documents_test() {
+  let mut random = PseudoRandom(2);
+  let base = random.document(5_000, 20);
+  let target = random.document(5_000, 20);
+  assert!(base.ops.len() > 100 && target.ops.len() > 100);
+  let delta = assert_diff(&base, &target);
+  assert_eq!(delta.utf16_base_len, base.utf16_target_len);
+}
@@ -0,0 +1,2 @@
+mod diff_test;
+mod transform_test;
@@ -0,0 +1,50 @@
+use lib_ot::core::{DeltaBuilder, DeltaOperations, EmptyAttributes, OperationTransform};
+
+const BASE: &str = "0123456789";
+
+fn delete_delta(start: usize, end: usize) -> DeltaOperations<EmptyAttributes> {
+  DeltaBuilder::new()
+    .retain(start)
+    .delete(end - start)
+    .retain(BASE.len() - end)
+    .build()
+}
+
+/// Transforms two concurrent deletes against each other and asserts that both peers converge
+/// to the [expected] string, regardless of which delete was applied first.
+fn assert_concurrent_deletes_converge(a: (usize, usize), b: (usize, usize), expected: &str) {
+  let base = DeltaBuilder::new().insert(BASE).build();
+  let delta_a = delete_delta(a.0, a.1);
+  let delta_b = delete_delta(b.0, b.1);
+
+  let (a_prime, b_prime) = delta_a.transform(&delta_b).unwrap();
+  let peer_a = base.compose(&delta_a).unwrap().compose(&b_prime).unwrap();
+  let peer_b = base.compose(&delta_b).unwrap().compose(&a_prime).unwrap();
+
+  assert_eq!(peer_a, peer_b);
+  assert_eq!(peer_a.content().unwrap(), expected);
+  assert_eq!(peer_a.utf16_target_len, expected.len());
+}
+
+#[test]
+fn transform_overlapping_deletes_test() {
+  assert_concurrent_deletes_converge((2, 6), (4, 8), "0189");
+  assert_concurrent_deletes_converge((4, 8), (2, 6), "0189");
+}
+
+#[test]
+fn transform_adjacent_deletes_test() {
+  assert_concurrent_deletes_converge((2, 4), (4, 6), "016789");
+  assert_concurrent_deletes_converge((4, 6), (2, 4), "016789");
+}
+
+#[test]
+fn transform_contained_deletes_test() {
+  assert_concurrent_deletes_converge((1, 9), (3, 5), "09");
+  assert_concurrent_deletes_converge((3, 5), (1, 9), "09");
+}
+
+#[test]
+fn transform_identical_deletes_test() {
+  assert_concurrent_deletes_converge((3, 7), (3, 7), "012789");
+}
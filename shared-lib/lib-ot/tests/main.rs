@@ -1 +1,2 @@
+mod delta;
 mod node;